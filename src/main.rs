@@ -1,5 +1,9 @@
 use chrono::prelude::*;
 use clap::{Parser, Subcommand};
+use git2::{Cred, CredentialType, IndexAddOption, PushOptions, RemoteCallbacks, Repository, StatusOptions};
+use glob::Pattern;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf, process::Command};
 
@@ -34,6 +38,21 @@ enum Commands {
     /// List recorded changes
     #[command(alias = "l")]
     List,
+    /// Generate a CHANGELOG.md section from recorded changes
+    #[command(alias = "cl")]
+    Changelog {
+        /// Path to the changelog file to prepend to
+        #[arg(short, long, default_value = "CHANGELOG.md")]
+        output: PathBuf,
+        /// Also list commits since this tag/commit, with the files each touched
+        #[arg(long)]
+        since: Option<String>,
+    },
+    /// Show a compact working-tree summary
+    #[command(alias = "s")]
+    Status,
+    /// Re-send the notification email for the last commit
+    Send,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,6 +61,23 @@ struct Change {
     type_: String,
     description: String,
     files: Vec<String>,
+    /// The fields below are filled in once `commit` realizes this change;
+    /// they stay `None` for changes still awaiting a commit.
+    #[serde(default)]
+    commit_hash: Option<String>,
+    #[serde(default)]
+    short_hash: Option<String>,
+    #[serde(default)]
+    author_name: Option<String>,
+    #[serde(default)]
+    author_email: Option<String>,
+    /// Whether the index had no staged changes of its own before `commit` staged these.
+    #[serde(default)]
+    tree_was_clean: Option<bool>,
+    /// Whether this change has already been rendered into a changelog, so
+    /// re-running `changelog` doesn't duplicate previously generated sections.
+    #[serde(default)]
+    changelogged: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -49,19 +85,367 @@ struct Config {
     default_branch: String,
     commit_templates: std::collections::HashMap<String, String>,
     auto_push: bool,
+    /// Glob patterns; only files matching one of these are tracked (empty = everything)
+    #[serde(default)]
+    include_paths: Vec<String>,
+    /// Glob patterns; files matching any of these are dropped, even if included above
+    #[serde(default)]
+    exclude_paths: Vec<String>,
+    /// "git" or "mercurial"; auto-detected from `.git`/`.hg` when unset
+    #[serde(default)]
+    backend: Option<String>,
+    /// Mail a commit/patch notification to these recipients after a successful push
+    #[serde(default)]
+    notify: Option<NotifyConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NotifyConfig {
+    smtp_host: String,
+    smtp_port: u16,
+    username: Option<String>,
+    password: Option<String>,
+    from: String,
+    recipients: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct RepoStatus {
+    branch: Option<String>,
+    ahead: u32,
+    behind: u32,
+    staged: u32,
+    modified: u32,
+    deleted: u32,
+    renamed: u32,
+    untracked: u32,
+    conflicted: u32,
+    stashes: u32,
+}
+
+impl RepoStatus {
+    fn render(&self) -> String {
+        let branch = self.branch.as_deref().unwrap_or("HEAD");
+        let mut parts = vec![branch.to_string()];
+
+        let mut push = |symbol: &str, count: u32| {
+            if count > 0 {
+                parts.push(format!("{}{}", symbol, count));
+            }
+        };
+        push("⇡", self.ahead);
+        push("⇣", self.behind);
+        push("+", self.staged);
+        push("!", self.modified);
+        push("-", self.deleted);
+        push("»", self.renamed);
+        push("?", self.untracked);
+        push("=", self.conflicted);
+        push("*", self.stashes);
+
+        parts.join(" ")
+    }
+}
+
+/// Repository operations `GitTracker` needs, abstracted so the "record changes,
+/// then commit" workflow isn't tied to git specifically.
+trait VcsBackend {
+    fn modified_files(&self) -> Vec<String>;
+    fn current_branch(&self) -> Option<String>;
+    /// Whether there are changes staged independently of what `stage_all` does.
+    fn has_staged_changes(&self) -> bool;
+    /// Stages all working-tree changes, returning whether anything was staged.
+    fn stage_all(&self) -> Result<bool, Box<dyn std::error::Error>>;
+    /// Commits staged changes, returning the new commit's full hash.
+    fn commit(&self, message: &str) -> Result<String, Box<dyn std::error::Error>>;
+    /// The hash of the current head commit, if any.
+    fn head_commit_hash(&self) -> Option<String>;
+    fn remote_exists(&self) -> bool;
+    fn branch_exists(&self, name: &str) -> bool;
+    fn push(&self, branch: &str) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+struct GitBackend {
+    repo: Repository,
+}
+
+impl GitBackend {
+    fn discover() -> Option<Self> {
+        Some(Self {
+            repo: Repository::discover(".").ok()?,
+        })
+    }
+}
+
+impl VcsBackend for GitBackend {
+    fn modified_files(&self) -> Vec<String> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(false).include_ignored(false);
+
+        let statuses = match self.repo.statuses(Some(&mut opts)) {
+            Ok(statuses) => statuses,
+            Err(_) => return Vec::new(),
+        };
+
+        statuses
+            .iter()
+            .filter(|entry| {
+                let status = entry.status();
+                status.is_wt_new()
+                    || status.is_wt_modified()
+                    || status.is_wt_deleted()
+                    || status.is_wt_renamed()
+                    || status.is_wt_typechange()
+                    || status.is_index_new()
+                    || status.is_index_modified()
+                    || status.is_index_deleted()
+                    || status.is_index_renamed()
+                    || status.is_index_typechange()
+            })
+            .filter_map(|entry| entry.path().map(String::from))
+            .collect()
+    }
+
+    fn current_branch(&self) -> Option<String> {
+        let head = self.repo.head().ok()?;
+        head.shorthand().map(String::from)
+    }
+
+    fn has_staged_changes(&self) -> bool {
+        let head_tree = self.repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+        self.repo
+            .diff_tree_to_index(head_tree.as_ref(), None, None)
+            .map(|diff| diff.deltas().len() > 0)
+            .unwrap_or(false)
+    }
+
+    fn stage_all(&self) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut index = self.repo.index()?;
+        index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+        Ok(self.has_staged_changes())
+    }
+
+    fn commit(&self, message: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let mut index = self.repo.index()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let signature = self.repo.signature()?;
+        let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+        let oid = self
+            .repo
+            .commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?;
+        Ok(oid.to_string())
+    }
+
+    fn head_commit_hash(&self) -> Option<String> {
+        Some(self.repo.head().ok()?.peel_to_commit().ok()?.id().to_string())
+    }
+
+    fn remote_exists(&self) -> bool {
+        self.repo.find_remote("origin").is_ok()
+    }
+
+    fn branch_exists(&self, name: &str) -> bool {
+        Command::new("git")
+            .args(["ls-remote", "--heads", "origin", name])
+            .output()
+            .map(|o| !o.stdout.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn push(&self, branch: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut remote = self.repo.find_remote("origin")?;
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(|_url, username_from_url, allowed_types| {
+            if allowed_types.contains(CredentialType::SSH_KEY) {
+                if let Some(username) = username_from_url {
+                    if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+                        return Ok(cred);
+                    }
+                }
+            }
+            if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Ok(token) = std::env::var("GIT_TRACKER_TOKEN") {
+                    return Cred::userpass_plaintext(&token, "");
+                }
+            }
+            Cred::default()
+        });
+
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        remote.push(&[&refspec], Some(&mut push_options))?;
+        Ok(())
+    }
+}
+
+struct HgBackend;
+
+impl HgBackend {
+    fn discover() -> Self {
+        Self
+    }
+
+    fn run(args: &[&str]) -> std::io::Result<std::process::Output> {
+        Command::new("hg").args(args).output()
+    }
+}
+
+impl VcsBackend for HgBackend {
+    fn modified_files(&self) -> Vec<String> {
+        let output = match Self::run(&["status"]) {
+            Ok(output) => output,
+            Err(_) => return Vec::new(),
+        };
+
+        // `hg status` prefixes each line with a one-letter code; skip "?" (untracked)
+        // and "I" (ignored) so this matches GitBackend's tracked-files-only semantics.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .filter(|(code, _)| *code != "?" && *code != "I")
+            .map(|(_, path)| path.to_string())
+            .collect()
+    }
+
+    fn current_branch(&self) -> Option<String> {
+        let output = Self::run(&["branch"]).ok()?;
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if branch.is_empty() {
+            None
+        } else {
+            Some(branch)
+        }
+    }
+
+    fn has_staged_changes(&self) -> bool {
+        // Mercurial has no staging area distinct from the working directory, so
+        // there's never a pre-existing "staged" state independent of what `commit` does.
+        false
+    }
+
+    fn stage_all(&self) -> Result<bool, Box<dyn std::error::Error>> {
+        // Mercurial has no staging area; `hg commit` acts on the working
+        // directory directly, so "staging" just means "is there anything to commit".
+        let output = Self::run(&["status"])?;
+        Ok(!output.stdout.is_empty())
+    }
+
+    fn commit(&self, message: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let status = Command::new("hg")
+            .args(["commit", "-A", "-m", message])
+            .status()?;
+        if !status.success() {
+            return Err("hg commit failed".into());
+        }
+        self.head_commit_hash().ok_or_else(|| "hg commit succeeded but no head revision was found".into())
+    }
+
+    fn head_commit_hash(&self) -> Option<String> {
+        let output = Self::run(&["log", "-r", ".", "--template", "{node}"]).ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if hash.is_empty() {
+            None
+        } else {
+            Some(hash)
+        }
+    }
+
+    fn remote_exists(&self) -> bool {
+        Self::run(&["paths", "default"])
+            .map(|o| o.status.success() && !o.stdout.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn branch_exists(&self, name: &str) -> bool {
+        Self::run(&["branches"])
+            .map(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .any(|line| line.split_whitespace().next() == Some(name))
+            })
+            .unwrap_or(false)
+    }
+
+    fn push(&self, _branch: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let status = Command::new("hg").arg("push").status()?;
+        if !status.success() {
+            return Err("hg push failed".into());
+        }
+        Ok(())
+    }
+}
+
+fn detect_backend() -> Option<Box<dyn VcsBackend>> {
+    if PathBuf::from(".hg").exists() {
+        Some(Box::new(HgBackend::discover()))
+    } else {
+        GitBackend::discover().map(|b| Box::new(b) as Box<dyn VcsBackend>)
+    }
+}
+
+fn backend_from_config(kind: Option<&str>) -> Option<Box<dyn VcsBackend>> {
+    match kind {
+        Some("mercurial") => Some(Box::new(HgBackend::discover())),
+        Some("git") => GitBackend::discover().map(|b| Box::new(b) as Box<dyn VcsBackend>),
+        _ => detect_backend(),
+    }
+}
+
+/// git-tracker's own state files. `.gt-config.json` can hold `NotifyConfig::password`
+/// in plaintext, so none of these must ever be staged into the repo they track.
+const STATE_FILES: [&str; 3] = [".gt-config.json", ".gt-changes.json", ".gt-history.json"];
+
+/// Makes sure the *target* repo's ignore file excludes git-tracker's own state
+/// files, so a plain `gt commit` can never sweep credentials into history.
+fn ensure_state_files_ignored(ignore_path: &PathBuf) {
+    let existing = fs::read_to_string(ignore_path).unwrap_or_default();
+    let missing: Vec<&str> = STATE_FILES
+        .iter()
+        .filter(|f| !existing.lines().any(|line| line.trim() == **f))
+        .copied()
+        .collect();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    let mut updated = existing;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    for f in missing {
+        updated.push_str(f);
+        updated.push('\n');
+    }
+    let _ = fs::write(ignore_path, updated);
 }
 
 struct GitTracker {
+    backend: Option<Box<dyn VcsBackend>>,
     config: Config,
     changes: Vec<Change>,
+    /// Changes that have already been realized into a commit, kept around so the
+    /// changelog has something to read once `changes` is cleared on commit.
+    history: Vec<Change>,
     // config_path: PathBuf,
     changes_path: PathBuf,
+    history_path: PathBuf,
 }
 
 impl GitTracker {
     fn new() -> Self {
         let config_path = PathBuf::from(".gt-config.json");
         let changes_path = PathBuf::from(".gt-changes.json");
+        let history_path = PathBuf::from(".gt-history.json");
 
         let config = if config_path.exists() {
             serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap()
@@ -79,6 +463,10 @@ impl GitTracker {
                 default_branch: "main".to_string(),
                 commit_templates: templates,
                 auto_push: true,
+                include_paths: Vec::new(),
+                exclude_paths: Vec::new(),
+                backend: None,
+                notify: None,
             };
             fs::write(&config_path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
             config
@@ -90,11 +478,31 @@ impl GitTracker {
             Vec::new()
         };
 
+        let history = if history_path.exists() {
+            serde_json::from_str(&fs::read_to_string(&history_path).unwrap()).unwrap()
+        } else {
+            Vec::new()
+        };
+
+        let backend = backend_from_config(config.backend.as_deref());
+
+        let is_mercurial = config.backend.as_deref() == Some("mercurial")
+            || (config.backend.is_none() && PathBuf::from(".hg").exists());
+        let ignore_path = if is_mercurial {
+            PathBuf::from(".hgignore")
+        } else {
+            PathBuf::from(".gitignore")
+        };
+        ensure_state_files_ignored(&ignore_path);
+
         Self {
+            backend,
             config,
             changes,
+            history,
             // config_path,
             changes_path,
+            history_path,
         }
     }
 
@@ -106,31 +514,165 @@ impl GitTracker {
         .unwrap();
     }
 
+    fn save_history(&self) {
+        fs::write(
+            &self.history_path,
+            serde_json::to_string_pretty(&self.history).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn path_is_tracked(&self, path: &str) -> bool {
+        let included = self.config.include_paths.is_empty()
+            || self
+                .config
+                .include_paths
+                .iter()
+                .filter_map(|p| Pattern::new(p).ok())
+                .any(|p| p.matches(path));
+
+        let excluded = self
+            .config
+            .exclude_paths
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .any(|p| p.matches(path));
+
+        included && !excluded
+    }
+
     fn get_modified_files(&self) -> Vec<String> {
+        let Some(backend) = self.backend.as_deref() else {
+            return Vec::new();
+        };
+
+        backend
+            .modified_files()
+            .into_iter()
+            .filter(|path| self.path_is_tracked(path))
+            .collect()
+    }
+
+    fn commit_cache_path(hash: &str) -> PathBuf {
+        PathBuf::from(".git/git-tracker/commits").join(hash)
+    }
+
+    fn read_commit_cache(hash: &str) -> Option<Vec<String>> {
+        let data = fs::read_to_string(Self::commit_cache_path(hash)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn write_commit_cache(hash: &str, files: &[String]) -> std::io::Result<()> {
+        // `.git/git-tracker` is only meaningful inside an actual git repo; don't
+        // materialize it (e.g. under Hg, or outside any repo) just to cache a lookup.
+        if !PathBuf::from(".git").exists() {
+            return Ok(());
+        }
+
+        let path = Self::commit_cache_path(hash);
+        fs::create_dir_all(path.parent().unwrap())?;
+
+        // Write to a per-process temp file and rename into place so concurrent
+        // `gt` invocations caching the same commit never see a half-written file.
+        let tmp_path = path.with_extension(format!("tmp.{}", std::process::id()));
+        fs::write(&tmp_path, serde_json::to_string(files)?)?;
+        fs::rename(&tmp_path, &path)
+    }
+
+    /// Files touched by `hash`, filtered by `include_paths`/`exclude_paths` and
+    /// cached under `.git/git-tracker/commits/<hash>` so repeated lookups (e.g.
+    /// building a changelog across a range) skip the `diff-tree` spawn.
+    fn get_files_for_commit(&self, hash: &str) -> Vec<String> {
+        if let Some(cached) = Self::read_commit_cache(hash) {
+            return cached;
+        }
+
         let output = Command::new("git")
-            .arg("diff")
-            .arg("--name-only")
+            .args(["diff-tree", "--no-commit-id", "--name-only", "-r", hash])
             .output()
             .expect("Failed to execute git command");
 
-        String::from_utf8_lossy(&output.stdout)
+        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
             .lines()
             .map(String::from)
             .filter(|s| !s.is_empty())
-            .collect()
+            .filter(|s| self.path_is_tracked(s))
+            .collect();
+
+        if let Err(err) = Self::write_commit_cache(hash, &files) {
+            eprintln!("⚠ Failed to cache files for commit {}: {}", hash, err);
+        }
+
+        files
+    }
+
+    fn get_git_config(key: &str) -> Option<String> {
+        let output = Command::new("git").args(["config", key]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
     }
 
-    fn get_current_branch(&self) -> Option<String> {
+    fn get_commit_subject(hash: &str) -> Option<String> {
         let output = Command::new("git")
-            .args(["branch", "--show-current"])
+            .args(["log", "-1", "--format=%s", hash])
             .output()
             .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
 
-        Some(
-            String::from_utf8_lossy(&output.stdout)
-                .trim()
-                .to_string(),
-        )
+    fn get_commit_patch(hash: &str) -> Option<String> {
+        let output = Command::new("git")
+            .args(["format-patch", "-1", "--stdout", hash])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Mails the commit `hash`'s subject and patch to `Config.notify`'s recipients,
+    /// if notifications are configured. A no-op otherwise.
+    fn notify_commit(&self, hash: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(notify) = &self.config.notify else {
+            return Ok(());
+        };
+
+        let subject = Self::get_commit_subject(hash).unwrap_or_else(|| hash.to_string());
+        let patch = Self::get_commit_patch(hash).unwrap_or_default();
+        let short_hash = &hash[..hash.len().min(7)];
+
+        let mut builder = Message::builder()
+            .from(notify.from.parse()?)
+            .subject(format!("[{}] {}", short_hash, subject));
+        for recipient in &notify.recipients {
+            builder = builder.to(recipient.parse()?);
+        }
+        let email = builder.body(patch)?;
+
+        let mut transport_builder =
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&notify.smtp_host)?
+                .port(notify.smtp_port);
+        if let (Some(username), Some(password)) = (&notify.username, &notify.password) {
+            transport_builder =
+                transport_builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        let transport = transport_builder.build();
+
+        tokio::runtime::Runtime::new()?.block_on(transport.send(email))?;
+
+        println!("✓ Notified {} recipient(s)", notify.recipients.len());
+        Ok(())
     }
 
     fn add_change(&mut self, description: String, type_: String) {
@@ -142,6 +684,12 @@ impl GitTracker {
             type_: type_.clone(),
             description: description.clone(),
             files: files.clone(),
+            commit_hash: None,
+            short_hash: None,
+            author_name: None,
+            author_email: None,
+            tree_was_clean: None,
+            changelogged: false,
         });
 
         self.save_changes();
@@ -176,122 +724,292 @@ impl GitTracker {
             .join("\n\n")
     }
 
+    fn get_repo_status(&self) -> RepoStatus {
+        let mut status = RepoStatus::default();
+
+        let output = Command::new("git")
+            .args(["status", "--porcelain=v2", "--branch"])
+            .output()
+            .expect("Failed to execute git command");
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some(head) = line.strip_prefix("# branch.head ") {
+                if head != "(detached)" {
+                    status.branch = Some(head.to_string());
+                }
+                continue;
+            }
+
+            if let Some(ab) = line.strip_prefix("# branch.ab ") {
+                for token in ab.split_whitespace() {
+                    if let Some(n) = token.strip_prefix('+') {
+                        status.ahead = n.parse().unwrap_or(0);
+                    } else if let Some(n) = token.strip_prefix('-') {
+                        status.behind = n.parse().unwrap_or(0);
+                    }
+                }
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("1") | Some("2") => {
+                    let xy = fields.next().unwrap_or("..");
+                    let mut chars = xy.chars();
+                    let x = chars.next().unwrap_or('.');
+                    let y = chars.next().unwrap_or('.');
+
+                    if x != '.' {
+                        status.staged += 1;
+                    }
+                    if y != '.' && y != 'D' {
+                        status.modified += 1;
+                    }
+                    if x == 'D' || y == 'D' {
+                        status.deleted += 1;
+                    }
+                    if line.starts_with('2') {
+                        status.renamed += 1;
+                    }
+                }
+                Some("u") => status.conflicted += 1,
+                Some("?") => status.untracked += 1,
+                _ => {}
+            }
+        }
+
+        let stash_output = Command::new("git")
+            .args(["stash", "list"])
+            .output()
+            .expect("Failed to execute git command");
+        status.stashes = String::from_utf8_lossy(&stash_output.stdout)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .count() as u32;
+
+        status
+    }
+
+    fn get_latest_tag(&self) -> Option<String> {
+        let output = Command::new("git")
+            .args(["describe", "--tags", "--abbrev=0"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if tag.is_empty() {
+            None
+        } else {
+            Some(tag)
+        }
+    }
+
+    fn changelog_section(type_: &str) -> &str {
+        match type_ {
+            "feature" => "Features",
+            "fix" => "Bug Fixes",
+            "docs" => "Documentation",
+            "refactor" => "Refactor",
+            "style" => "Style",
+            "test" => "Tests",
+            "chore" => "Chores",
+            other => other,
+        }
+    }
+
+    fn generate_changelog(&self) -> String {
+        let version = self.get_latest_tag().unwrap_or_else(|| "Unreleased".to_string());
+
+        let mut sections: Vec<(&str, Vec<&Change>)> = Vec::new();
+        let mut latest_timestamp: Option<&str> = None;
+        for change in self.history.iter().chain(self.changes.iter()).filter(|c| !c.changelogged) {
+            let is_newer = match latest_timestamp {
+                Some(latest) => change.timestamp.as_str() > latest,
+                None => true,
+            };
+            if is_newer {
+                latest_timestamp = Some(&change.timestamp);
+            }
+
+            let title = Self::changelog_section(&change.type_);
+            match sections.iter_mut().find(|(name, _)| *name == title) {
+                Some((_, entries)) => entries.push(change),
+                None => sections.push((title, vec![change])),
+            }
+        }
+
+        if sections.is_empty() {
+            return String::new();
+        }
+
+        let date = latest_timestamp
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+
+        let mut out = format!("## [{}] - {}\n", version, date);
+        for (title, entries) in sections {
+            out.push_str(&format!("\n### {}\n\n", title));
+            for change in entries {
+                match &change.short_hash {
+                    Some(short_hash) => out.push_str(&format!("- {} ({})\n", change.description, short_hash)),
+                    None => out.push_str(&format!("- {}\n", change.description)),
+                }
+                for file in &change.files {
+                    out.push_str(&format!("  - {}\n", file));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn list_commits_since(since: &str) -> Vec<String> {
+        let output = Command::new("git")
+            .args(["rev-list", &format!("{since}..HEAD")])
+            .output()
+            .expect("Failed to execute git command");
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(String::from)
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Renders an extra "### Commits" section listing every commit since `since`
+    /// along with the files it touched, using `get_files_for_commit`'s cache.
+    fn generate_range_changelog(&self, since: &str) -> String {
+        let commits = Self::list_commits_since(since);
+        if commits.is_empty() {
+            return String::new();
+        }
+
+        let mut out = String::from("\n### Commits\n\n");
+        for hash in commits.iter().rev() {
+            let subject = Self::get_commit_subject(hash).unwrap_or_else(|| hash.clone());
+            let short_hash = hash.chars().take(7).collect::<String>();
+            out.push_str(&format!("- {} ({})\n", subject, short_hash));
+            for file in self.get_files_for_commit(hash) {
+                out.push_str(&format!("  - {}\n", file));
+            }
+        }
+        out
+    }
+
+    /// Prepends a fresh changelog section covering only changes not yet rendered
+    /// by a previous run, returning whether anything was actually written.
+    fn write_changelog(&mut self, path: &PathBuf, since: Option<&str>) -> std::io::Result<bool> {
+        let mut new_section = self.generate_changelog();
+        if let Some(since) = since {
+            new_section.push_str(&self.generate_range_changelog(since));
+        }
+
+        if new_section.is_empty() {
+            return Ok(false);
+        }
+
+        let existing = fs::read_to_string(path).unwrap_or_default();
+        fs::write(path, format!("{}\n{}", new_section, existing))?;
+
+        for change in self.history.iter_mut().chain(self.changes.iter_mut()) {
+            change.changelogged = true;
+        }
+        self.save_history();
+        self.save_changes();
+
+        Ok(true)
+    }
+
     fn commit_and_push(&mut self, branch: Option<String>, no_push: bool) -> Result<(), Box<dyn std::error::Error>> {
         if self.changes.is_empty() {
             println!("No changes to commit");
             return Ok(());
         }
-    
-        // Verify we're in a git repository
-        if !Command::new("git")
-            .args(["rev-parse", "--git-dir"])
-            .status()?
-            .success()
-        {
-            println!("❌ Not in a git repository");
+
+        let Some(backend) = self.backend.as_deref() else {
+            println!("❌ Not in a git or Mercurial repository");
             return Ok(());
-        }
-    
-        // Check if there are any git changes to commit
-        let status_output = Command::new("git")
-            .args(["status", "--porcelain"])
-            .output()?;
-    
-        if status_output.stdout.is_empty() {
-            println!("No git changes detected to commit");
+        };
+
+        // Check if there are any changes to commit
+        if backend.modified_files().is_empty() {
+            println!("No changes detected to commit");
             return Ok(());
         }
-    
+
         // Determine target branch
-        let branch = branch.or_else(|| self.get_current_branch()).unwrap_or_else(|| self.config.default_branch.clone());
-    
+        let branch = branch.or_else(|| backend.current_branch()).unwrap_or_else(|| self.config.default_branch.clone());
+
         // Stage changes
         println!("Staging changes...");
-        if !Command::new("git").arg("add").arg(".").status()?.success() {
-            println!("❌ Failed to stage changes");
-            return Ok(());
-        }
-    
-        // Verify files were staged
-        let staged_output = Command::new("git")
-            .args(["diff", "--cached", "--quiet"])
-            .status()?;
-    
-        if staged_output.success() {
+        let tree_was_clean = !backend.has_staged_changes();
+        if !backend.stage_all()? {
             println!("❌ No changes were staged");
             return Ok(());
         }
-    
+
         // Generate and verify commit message
         let commit_message = self.generate_commit_message();
         if commit_message.is_empty() {
             println!("❌ Empty commit message, nothing to commit");
             return Ok(());
         }
-    
+
         // Commit changes
         println!("Committing changes...");
-        if !Command::new("git")
-            .arg("commit")
-            .arg("-m")
-            .arg(&commit_message)
-            .status()?
-            .success()
-        {
-            println!("❌ Failed to commit changes");
-            return Ok(());
+        let hash = backend.commit(&commit_message)?;
+
+        // Record who/what realized this commit against the changes it came from,
+        // persisting the enriched log before it's cleared below.
+        let short_hash = hash.chars().take(7).collect::<String>();
+        let author_name = Self::get_git_config("user.name");
+        let author_email = Self::get_git_config("user.email");
+
+        for change in &mut self.changes {
+            change.commit_hash = Some(hash.clone());
+            change.short_hash = Some(short_hash.clone());
+            change.author_name = author_name.clone();
+            change.author_email = author_email.clone();
+            change.tree_was_clean = Some(tree_was_clean);
         }
-    
+
+        // Move the now-realized changes into the permanent history the changelog
+        // reads from, since `changes` only tracks what's still pending a commit.
+        self.history.append(&mut self.changes);
+        self.save_history();
+        self.save_changes();
+
         // Push if enabled
         if !no_push && self.config.auto_push {
             println!("Pushing to remote...");
-            
-            // Check if remote exists
-            if !Command::new("git")
-                .args(["remote", "get-url", "origin"])
-                .status()?
-                .success()
-            {
-                println!("❌ Remote 'origin' not found");
+
+            if !backend.remote_exists() {
+                println!("❌ Remote not found");
                 println!("✓ Changes committed successfully (push skipped - no remote)");
-                self.changes.clear();
-                self.save_changes();
                 return Ok(());
             }
-    
-            // Determine push arguments based on remote branch existence
-            let remote_branch_exists = Command::new("git")
-                .args(["ls-remote", "--heads", "origin", &branch])
-                .output()?
-                .stdout
-                .len() > 0;
-    
-            let push_args = if !remote_branch_exists {
+
+            if !backend.branch_exists(&branch) {
                 println!("Creating new remote branch '{}'...", branch);
-                vec!["push", "-u", "origin", &branch]
-            } else {
-                vec!["push", "origin", &branch]
-            };
-    
-            if Command::new("git").args(&push_args).status()?.success() {
-                println!("✓ Successfully pushed changes to {}", branch);
-            } else {
-                println!("❌ Failed to push changes to remote");
-                println!("  Your commits are saved locally. To push later, run:");
-                println!("  git push origin {}", branch);
-                return Ok(());
+            }
+
+            backend.push(&branch)?;
+            println!("✓ Successfully pushed changes to {}", branch);
+
+            if let Err(err) = self.notify_commit(&hash) {
+                eprintln!("⚠ Failed to send commit notification: {}", err);
             }
         } else {
             println!("✓ Successfully committed changes (push skipped)");
         }
-    
-        // Clear tracked changes only after successful operations
-        self.changes.clear();
-        self.save_changes();
-    
+
         Ok(())
     }
-    
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -306,11 +1024,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             tracker.commit_and_push(branch, no_push)?;
         }
         Commands::List => {
-            if tracker.changes.is_empty() {
+            if tracker.history.is_empty() && tracker.changes.is_empty() {
                 println!("No changes recorded yet");
             } else {
                 println!("\nRecorded changes:");
-                for (i, change) in tracker.changes.iter().enumerate() {
+                for (i, change) in tracker.history.iter().chain(tracker.changes.iter()).enumerate() {
                     println!(
                         "{}. [{}] {}: {}",
                         i + 1,
@@ -321,9 +1039,26 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     if !change.files.is_empty() {
                         println!("   Files: {}", change.files.join(", "));
                     }
+                    if let (Some(short_hash), Some(author)) = (&change.short_hash, &change.author_name) {
+                        println!("   Commit: {} by {}", short_hash, author);
+                    }
                 }
             }
         }
+        Commands::Status => {
+            println!("{}", tracker.get_repo_status().render());
+        }
+        Commands::Send => match tracker.backend.as_deref().and_then(|b| b.head_commit_hash()) {
+            Some(hash) => tracker.notify_commit(&hash)?,
+            None => println!("❌ No commits found"),
+        },
+        Commands::Changelog { output, since } => {
+            if tracker.write_changelog(&output, since.as_deref())? {
+                println!("✓ Updated {}", output.display());
+            } else {
+                println!("No changes recorded");
+            }
+        }
     }
 
     Ok(())